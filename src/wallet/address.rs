@@ -13,13 +13,15 @@
 
 //! # Addresses
 //!
-//! Support for ordinary base58 Bitcoin addresses
+//! Support for ordinary base58 and bech32/bech32m Bitcoin addresses
 //!
 
 use secp256k1::key::PublicKey;
 use crypto::digest::Digest;
 use crypto::sha2::Sha256;
-use std::ops;
+use std::fmt;
+
+use bech32::{self, u5, FromBase32, ToBase32, Variant};
 
 use blockdata::script::Script;
 use blockdata::opcodes;
@@ -27,26 +29,205 @@ use network::constants::Network;
 use util::hash::Ripemd160Hash;
 use util::base58::{self, FromBase58, ToBase58};
 
+/// The different kinds of data that may be encoded in an address
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Payload {
+  /// P2PKH address
+  PubkeyHash(Ripemd160Hash),
+  /// P2SH address
+  ScriptHash(Ripemd160Hash),
+  /// Segwit address
+  ///
+  /// `Payload::new_witness_program` and the `Address` decoding and `from_key` constructors only
+  /// ever produce a `version` in the range 0-16. Building the variant directly via its struct
+  /// literal does not check `version`: for a version above 16, `Address::script_pubkey` panics,
+  /// and `Address::to_bech32` returns `None` only once it is above 31 (the `Display` impl then
+  /// panics). Code within this module builds
+  /// it directly only with an already-validated version (the constant 0 used by the P2WPKH
+  /// constructors).
+  WitnessProgram {
+    /// The witness program version
+    version: u8,
+    /// The witness program bytes
+    program: Vec<u8>
+  }
+}
+
+impl Payload {
+  /// Constructs a witness program payload, validating that `version` is in the allowed 0-16
+  /// range and that `program` is 2-40 bytes (20 or 32 bytes for version 0), per BIP141/BIP173.
+  pub fn new_witness_program(version: u8, program: Vec<u8>) -> Result<Payload, Error> {
+    if version > 16 {
+      return Err(Error::InvalidWitnessVersion(version));
+    }
+    if program.len() < 2 || program.len() > 40 {
+      return Err(Error::InvalidWitnessProgramLength(program.len()));
+    }
+    if version == 0 && program.len() != 20 && program.len() != 32 {
+      return Err(Error::InvalidSegwitV0ProgramLength(program.len()));
+    }
+    Ok(Payload::WitnessProgram { version: version, program: program })
+  }
+}
+
+/// The kind of standard script a given address pays to, used by `Address::address_type`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AddressType {
+  /// Pay to pubkey hash
+  P2pkh,
+  /// Pay to script hash
+  P2sh,
+  /// Pay to witness pubkey hash
+  P2wpkh,
+  /// Pay to witness script hash
+  P2wsh
+}
+
+/// An error encountered while parsing or building an address
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Error {
+  /// Error while decoding base58
+  Base58(base58::Error),
+  /// Error while decoding bech32
+  Bech32(bech32::Error),
+  /// The bech32 human-readable part did not match a known network prefix
+  InvalidHrp(String),
+  /// The witness version byte was out of the allowed 0-16 range
+  InvalidWitnessVersion(u8),
+  /// The witness program was not 2-40 bytes long
+  InvalidWitnessProgramLength(usize),
+  /// A v0 witness program was not 20 or 32 bytes long
+  InvalidSegwitV0ProgramLength(usize),
+  /// The checksum variant (bech32 vs bech32m) didn't match what the witness version requires
+  InvalidChecksumVariant
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      Error::Base58(ref e) => write!(f, "base58 error: {}", e),
+      Error::Bech32(ref e) => write!(f, "bech32 error: {}", e),
+      Error::InvalidHrp(ref hrp) => write!(f, "unrecognized bech32 human-readable part `{}`", hrp),
+      Error::InvalidWitnessVersion(v) => write!(f, "invalid witness version: {}", v),
+      Error::InvalidWitnessProgramLength(l) => write!(f, "invalid witness program length: {}", l),
+      Error::InvalidSegwitV0ProgramLength(l) => write!(f, "invalid segwit v0 program length: {}", l),
+      Error::InvalidChecksumVariant => write!(f, "bech32/bech32m checksum does not match witness version")
+    }
+  }
+}
+
 #[derive(Clone, PartialEq, Eq)]
 /// A Bitcoin address
 pub struct Address {
   /// The network on which this address is usable
   pub network: Network,
-  /// The pubkeyhash that this address encodes
-  pub hash: Ripemd160Hash
+  /// The type of data encoded in this address
+  pub payload: Payload
+}
+
+fn bech32_hrp(network: Network) -> &'static str {
+  match network {
+    Network::Bitcoin => "bc",
+    Network::Testnet => "tb"
+  }
+}
+
+fn hrp_to_network(hrp: &str) -> Option<Network> {
+  match hrp {
+    "bc" => Some(Network::Bitcoin),
+    "tb" => Some(Network::Testnet),
+    _ => None
+  }
+}
+
+fn hash160(data: &[u8]) -> Ripemd160Hash {
+  let mut sha = Sha256::new();
+  let mut out = [0;32];
+  sha.input(data);
+  sha.result(&mut out);
+  Ripemd160Hash::from_data(&out)
+}
+
+/// Pushes a small integer (the witness version) the same way the script interpreter expects it:
+/// `OP_0` for version 0, `OP_1`..`OP_16` for versions 1 through 16.
+///
+/// # Panics
+///
+/// Panics if `version > 16`. The checked ways to build a `Payload::WitnessProgram` (the only
+/// caller of this function) never produce such a version; one built directly via its struct
+/// literal with a larger version reaches this panic through `Address::script_pubkey`.
+fn push_witness_version(script: &mut Script, version: u8) {
+  let opcode = match version {
+    0  => opcodes::All::OP_0,
+    1  => opcodes::All::OP_1,
+    2  => opcodes::All::OP_2,
+    3  => opcodes::All::OP_3,
+    4  => opcodes::All::OP_4,
+    5  => opcodes::All::OP_5,
+    6  => opcodes::All::OP_6,
+    7  => opcodes::All::OP_7,
+    8  => opcodes::All::OP_8,
+    9  => opcodes::All::OP_9,
+    10 => opcodes::All::OP_10,
+    11 => opcodes::All::OP_11,
+    12 => opcodes::All::OP_12,
+    13 => opcodes::All::OP_13,
+    14 => opcodes::All::OP_14,
+    15 => opcodes::All::OP_15,
+    16 => opcodes::All::OP_16,
+    _ => panic!("witness version {} is outside the 0-16 range", version)
+  };
+  script.push_opcode(opcode);
 }
 
 impl Address {
-  /// Creates an address from a public key
+  /// Creates a pay-to-pubkey-hash (P2PKH) address from a public key
   #[inline]
   pub fn from_key(network: Network, pk: &PublicKey) -> Address {
-    let mut sha = Sha256::new();
-    let mut out = [0;32];
-    sha.input(&pk[..]);
-    sha.result(&mut out);
     Address {
       network: network,
-      hash: Ripemd160Hash::from_data(&out)
+      payload: Payload::PubkeyHash(hash160(&pk[..]))
+    }
+  }
+
+  /// Creates a pay-to-witness-pubkey-hash (P2WPKH) address from a public key
+  #[inline]
+  pub fn p2wpkh_from_key(network: Network, pk: &PublicKey) -> Address {
+    Address {
+      network: network,
+      payload: Payload::WitnessProgram {
+        version: 0,
+        program: hash160(&pk[..])[..].to_vec()
+      }
+    }
+  }
+
+  /// Creates a P2SH-wrapped P2WPKH address from a public key
+  #[inline]
+  pub fn p2shwpkh_from_key(network: Network, pk: &PublicKey) -> Address {
+    // The P2WPKH redeem script is `OP_0 <20-byte-hash160-of-pubkey>`.
+    let program = hash160(&pk[..]);
+    let mut redeem_script_bytes = vec![0u8, program.len() as u8];
+    redeem_script_bytes.extend(&program[..]);
+
+    Address {
+      network: network,
+      payload: Payload::ScriptHash(hash160(&redeem_script_bytes))
+    }
+  }
+
+  /// Returns the type of address this is, or `None` if it does not correspond to one of the
+  /// known standard kinds (e.g. a witness program of an unrecognized version/length).
+  pub fn address_type(&self) -> Option<AddressType> {
+    match self.payload {
+      Payload::PubkeyHash(_) => Some(AddressType::P2pkh),
+      Payload::ScriptHash(_) => Some(AddressType::P2sh),
+      Payload::WitnessProgram { version: 0, ref program } => match program.len() {
+        20 => Some(AddressType::P2wpkh),
+        32 => Some(AddressType::P2wsh),
+        _ => None
+      },
+      Payload::WitnessProgram { .. } => None
     }
   }
 
@@ -54,55 +235,149 @@ impl Address {
   #[inline]
   pub fn script_pubkey(&self) -> Script {
     let mut script = Script::new();
-    script.push_opcode(opcodes::All::OP_DUP);
-    script.push_opcode(opcodes::All::OP_HASH160);
-    script.push_slice(&self.hash[..]);
-    script.push_opcode(opcodes::All::OP_EQUALVERIFY);
-    script.push_opcode(opcodes::All::OP_CHECKSIG);
+    match self.payload {
+      Payload::PubkeyHash(ref hash) => {
+        script.push_opcode(opcodes::All::OP_DUP);
+        script.push_opcode(opcodes::All::OP_HASH160);
+        script.push_slice(&hash[..]);
+        script.push_opcode(opcodes::All::OP_EQUALVERIFY);
+        script.push_opcode(opcodes::All::OP_CHECKSIG);
+      }
+      Payload::ScriptHash(ref hash) => {
+        script.push_opcode(opcodes::All::OP_HASH160);
+        script.push_slice(&hash[..]);
+        script.push_opcode(opcodes::All::OP_EQUAL);
+      }
+      Payload::WitnessProgram { version, ref program } => {
+        push_witness_version(&mut script, version);
+        script.push_slice(&program[..]);
+      }
+    }
     script
   }
-}
 
-impl ops::Index<usize> for Address {
-  type Output = u8;
-  #[inline]
-  fn index(&self, index: usize) -> &u8 {
-    &self.hash[index]
+  /// Reconstructs an address from a script pubkey, matching it against the standard P2PKH, P2SH
+  /// and witness-program output templates. Returns `None` for anything non-standard.
+  pub fn from_script(script: &Script, network: Network) -> Option<Address> {
+    let bytes: &[u8] = &script[..];
+
+    // OP_DUP OP_HASH160 <20> OP_EQUALVERIFY OP_CHECKSIG
+    if bytes.len() == 25
+      && bytes[0] == opcodes::All::OP_DUP as u8
+      && bytes[1] == opcodes::All::OP_HASH160 as u8
+      && bytes[2] == 20
+      && bytes[23] == opcodes::All::OP_EQUALVERIFY as u8
+      && bytes[24] == opcodes::All::OP_CHECKSIG as u8
+    {
+      return Some(Address {
+        network: network,
+        payload: Payload::PubkeyHash(Ripemd160Hash::from_slice(&bytes[3..23]))
+      });
+    }
+
+    // OP_HASH160 <20> OP_EQUAL
+    if bytes.len() == 23
+      && bytes[0] == opcodes::All::OP_HASH160 as u8
+      && bytes[1] == 20
+      && bytes[22] == opcodes::All::OP_EQUAL as u8
+    {
+      return Some(Address {
+        network: network,
+        payload: Payload::ScriptHash(Ripemd160Hash::from_slice(&bytes[2..22]))
+      });
+    }
+
+    // OP_<0..16> <2..40 bytes, or 20/32 bytes for version 0>
+    if bytes.len() >= 4 {
+      let version = match bytes[0] {
+        0x00 => Some(0u8),
+        v @ 0x51...0x60 => Some(v - 0x50),
+        _ => None
+      };
+      if let Some(version) = version {
+        let push_len = bytes[1] as usize;
+        if bytes.len() == 2 + push_len {
+          // `Payload::new_witness_program` applies the same length rules (2-40 bytes, and
+          // exactly 20 or 32 for version 0) that `Address::from_bech32` enforces, so a script
+          // that wouldn't round-trip through `to_bech32` is rejected here too.
+          if let Ok(payload) = Payload::new_witness_program(version, bytes[2..].to_vec()) {
+            return Some(Address { network: network, payload: payload });
+          }
+        }
+      }
+    }
+
+    None
   }
-}
 
-impl ops::Index<ops::Range<usize>> for Address {
-  type Output = [u8];
-  #[inline]
-  fn index(&self, index: ops::Range<usize>) -> &[u8] {
-    &self.hash[index]
+  /// Encodes this address using bech32/bech32m. Returns `None` unless the payload is a witness
+  /// program, since legacy payloads are only ever encoded as base58check.
+  pub fn to_bech32(&self) -> Option<String> {
+    match self.payload {
+      Payload::WitnessProgram { version, ref program } => {
+        // The checked constructors keep `version` in 0-16 (see `Payload::WitnessProgram`), but a
+        // directly built payload may hold a larger one. Versions 17-31 are encoded as they are;
+        // above 31, `?` turns the failed conversion into `None` rather than a panic.
+        let version_u5 = u5::try_from_u8(version).ok()?;
+        let mut data = vec![version_u5];
+        data.extend(program.to_base32());
+        let variant = if version == 0 { Variant::Bech32 } else { Variant::Bech32m };
+        bech32::encode(bech32_hrp(self.network), data, variant).ok()
+      }
+      _ => None
+    }
   }
-}
 
-impl ops::Index<ops::RangeTo<usize>> for Address {
-  type Output = [u8];
-  #[inline]
-  fn index(&self, index: ops::RangeTo<usize>) -> &[u8] {
-    &self.hash[index]
+  fn from_bech32(s: &str) -> Result<Address, Error> {
+    let (hrp, data, variant) = bech32::decode(s).map_err(Error::Bech32)?;
+    let network = hrp_to_network(&hrp).ok_or_else(|| Error::InvalidHrp(hrp))?;
+
+    if data.is_empty() {
+      return Err(Error::InvalidWitnessProgramLength(0));
+    }
+    let version = data[0].to_u8();
+    let program = Vec::<u8>::from_base32(&data[1..]).map_err(Error::Bech32)?;
+    let payload = Payload::new_witness_program(version, program)?;
+
+    let expected_variant = if version == 0 { Variant::Bech32 } else { Variant::Bech32m };
+    if variant != expected_variant {
+      return Err(Error::InvalidChecksumVariant);
+    }
+
+    Ok(Address { network: network, payload: payload })
   }
-}
 
-impl ops::Index<ops::RangeFrom<usize>> for Address {
-  type Output = [u8];
-  #[inline]
-  fn index(&self, index: ops::RangeFrom<usize>) -> &[u8] {
-    &self.hash[index]
+  /// Encodes this address as base58check. Returns `None` for a witness program payload, which is
+  /// only ever encoded as bech32/bech32m; see `to_bech32`.
+  ///
+  /// This shadows (and is implemented in terms of) the `ToBase58` trait impl below so that
+  /// calling it directly on an `Address` can never panic, regardless of payload.
+  pub fn to_base58check(&self) -> Option<String> {
+    match self.payload {
+      Payload::WitnessProgram { .. } => None,
+      Payload::PubkeyHash(_) | Payload::ScriptHash(_) =>
+        Some(ToBase58::to_base58check(self))
+    }
   }
-}
 
-impl ops::Index<ops::RangeFull> for Address {
-  type Output = [u8];
-  #[inline]
-  fn index(&self, _: ops::RangeFull) -> &[u8] {
-    &self.hash[..]
+  /// Parses an address from its string representation: as bech32/bech32m when it starts with
+  /// `bc1` or `tb1` (in either case), and as base58check otherwise. There is no fallback from one
+  /// format to the other.
+  pub fn from_str(s: &str) -> Result<Address, Error> {
+    let looks_bech32 = s.to_lowercase().starts_with("bc1") || s.to_lowercase().starts_with("tb1");
+    if looks_bech32 {
+      Address::from_bech32(s)
+    } else {
+      FromBase58::from_base58check(s).map_err(Error::Base58)
+    }
   }
 }
 
+impl ::std::str::FromStr for Address {
+  type Err = Error;
+  fn from_str(s: &str) -> Result<Address, Error> { Address::from_str(s) }
+}
+
 /// Conversion from other types into an address
 pub trait ToAddress {
   /// Copies `self` into a new `Address`
@@ -114,20 +389,33 @@ impl<'a> ToAddress for &'a [u8] {
   fn to_address(&self, network: Network) -> Address {
     Address {
       network: network,
-      hash: Ripemd160Hash::from_slice(*self)
+      payload: Payload::PubkeyHash(Ripemd160Hash::from_slice(*self))
     }
   }
 }
 
+// `ToBase58` has no room for a witness-program payload in its 21-byte version+hash layout, and
+// its methods can't return `Result`. `Address::to_base58check` (an inherent method, which takes
+// priority over this trait impl in method-call syntax) never calls into this trait for a witness
+// program. Calling the trait's methods directly (e.g. `ToBase58::to_base58check(&addr)`) on an
+// address with a witness-program payload reaches the panics below.
 impl ToBase58 for Address {
   fn base58_layout(&self) -> Vec<u8> {
     let mut ret = vec![
-      match self.network {
-        Network::Bitcoin => 0,
-        Network::Testnet => 111
+      match (self.network, &self.payload) {
+        (Network::Bitcoin, &Payload::PubkeyHash(_)) => 0,
+        (Network::Testnet, &Payload::PubkeyHash(_)) => 111,
+        (Network::Bitcoin, &Payload::ScriptHash(_)) => 5,
+        (Network::Testnet, &Payload::ScriptHash(_)) => 196,
+        (_, &Payload::WitnessProgram { .. }) =>
+          unreachable!("Address::to_base58check never calls into ToBase58 for a witness program")
       }
     ];
-    ret.push_all(&self.hash[..]);
+    match self.payload {
+      Payload::PubkeyHash(ref hash) => ret.extend(&hash[..]),
+      Payload::ScriptHash(ref hash) => ret.extend(&hash[..]),
+      Payload::WitnessProgram { .. } => unreachable!("see note above")
+    }
     ret
   }
 }
@@ -138,20 +426,33 @@ impl FromBase58 for Address {
       return Err(base58::Error::InvalidLength(data.len()));
     }
 
-    Ok(Address {
-      network: match data[0] {
-        0   => Network::Bitcoin,
-        111 => Network::Testnet,
-        x   => { return Err(base58::Error::InvalidVersion(vec![x])); }
-      },
-      hash: Ripemd160Hash::from_slice(&data[1..])
-    })
+    let hash = Ripemd160Hash::from_slice(&data[1..]);
+    let (network, payload) = match data[0] {
+      0   => (Network::Bitcoin, Payload::PubkeyHash(hash)),
+      111 => (Network::Testnet, Payload::PubkeyHash(hash)),
+      5   => (Network::Bitcoin, Payload::ScriptHash(hash)),
+      196 => (Network::Testnet, Payload::ScriptHash(hash)),
+      x   => { return Err(base58::Error::InvalidVersion(vec![x])); }
+    };
+
+    Ok(Address { network: network, payload: payload })
+  }
+}
+
+impl fmt::Display for Address {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self.payload {
+      Payload::WitnessProgram { .. } =>
+        write!(f, "{}", self.to_bech32().expect("witness programs always encode to bech32")),
+      Payload::PubkeyHash(_) | Payload::ScriptHash(_) =>
+        write!(f, "{}", self.to_base58check().expect("legacy payloads always encode to base58check"))
+    }
   }
 }
 
-impl ::std::fmt::Debug for Address {
-  fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
-    write!(f, "{}", self.to_base58check())
+impl fmt::Debug for Address {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fmt::Display::fmt(self, f)
   }
 }
 
@@ -165,19 +466,108 @@ mod tests {
   use network::constants::Network::Bitcoin;
   use util::hash::Ripemd160Hash;
   use util::base58::{FromBase58, ToBase58};
-  use super::Address;
+  use super::{Address, Payload, AddressType, Error, Script};
+  use blockdata::opcodes;
 
   #[test]
   fn test_address_58() {
     let addr = Address {
       network: Bitcoin,
-      hash: Ripemd160Hash::from_slice(&"162c5ea71c0b23f5b9022ef047c4a86470a5b070".from_hex().unwrap())
+      payload: Payload::PubkeyHash(
+        Ripemd160Hash::from_slice(&"162c5ea71c0b23f5b9022ef047c4a86470a5b070".from_hex().unwrap())
+      )
     };
 
-    assert_eq!(&addr.to_base58check(), "132F25rTsvBdp9JzLLBHP5mvGY66i1xdiM");
+    assert_eq!(&addr.to_base58check().unwrap(), "132F25rTsvBdp9JzLLBHP5mvGY66i1xdiM");
     assert_eq!(FromBase58::from_base58check("132F25rTsvBdp9JzLLBHP5mvGY66i1xdiM"), Ok(addr));
   }
 
+  #[test]
+  fn test_p2sh_58() {
+    let addr = Address {
+      network: Bitcoin,
+      payload: Payload::ScriptHash(
+        Ripemd160Hash::from_slice(&"162c5ea71c0b23f5b9022ef047c4a86470a5b070".from_hex().unwrap())
+      )
+    };
+
+    assert_eq!(addr.address_type(), Some(AddressType::P2sh));
+    assert_eq!(FromBase58::from_base58check(&addr.to_base58check().unwrap()), Ok(addr));
+  }
+
+  #[test]
+  fn test_new_witness_program_validates_version() {
+    assert!(Payload::new_witness_program(16, vec![0; 20]).is_ok());
+    assert_eq!(Payload::new_witness_program(17, vec![0; 20]),
+               Err(Error::InvalidWitnessVersion(17)));
+  }
+
+  #[test]
+  fn test_witness_to_base58check_is_none() {
+    let addr = Address {
+      network: Bitcoin,
+      payload: Payload::WitnessProgram {
+        version: 0,
+        program: "751e76e8199196d454941c45d1b3a323f1433bd6".from_hex().unwrap()
+      }
+    };
+    assert_eq!(addr.to_base58check(), None);
+  }
+
+  #[test]
+  fn test_from_script_roundtrip() {
+    let p2pkh = Address {
+      network: Bitcoin,
+      payload: Payload::PubkeyHash(
+        Ripemd160Hash::from_slice(&"162c5ea71c0b23f5b9022ef047c4a86470a5b070".from_hex().unwrap())
+      )
+    };
+    assert_eq!(Address::from_script(&p2pkh.script_pubkey(), Bitcoin), Some(p2pkh));
+
+    let p2sh = Address {
+      network: Bitcoin,
+      payload: Payload::ScriptHash(
+        Ripemd160Hash::from_slice(&"162c5ea71c0b23f5b9022ef047c4a86470a5b070".from_hex().unwrap())
+      )
+    };
+    assert_eq!(Address::from_script(&p2sh.script_pubkey(), Bitcoin), Some(p2sh));
+
+    let p2wpkh = Address {
+      network: Bitcoin,
+      payload: Payload::WitnessProgram {
+        version: 0,
+        program: "751e76e8199196d454941c45d1b3a323f1433bd6".from_hex().unwrap()
+      }
+    };
+    assert_eq!(Address::from_script(&p2wpkh.script_pubkey(), Bitcoin), Some(p2wpkh));
+  }
+
+  #[test]
+  fn test_from_script_rejects_nonstandard_v0_length() {
+    // `OP_0 <22 bytes>`: a version-0 witness program that isn't 20 or 32 bytes, and so can never
+    // be produced by `to_bech32`/`from_bech32` either - `from_script` must reject it too.
+    let mut script = Script::new();
+    script.push_opcode(opcodes::All::OP_0);
+    script.push_slice(&[0u8; 22]);
+
+    assert_eq!(Address::from_script(&script, Bitcoin), None);
+  }
+
+  #[test]
+  fn test_bech32_roundtrip() {
+    let addr = Address {
+      network: Bitcoin,
+      payload: Payload::WitnessProgram {
+        version: 0,
+        program: "751e76e8199196d454941c45d1b3a323f1433bd6".from_hex().unwrap()
+      }
+    };
+
+    assert_eq!(addr.address_type(), Some(AddressType::P2wpkh));
+    let encoded = addr.to_bech32().unwrap();
+    assert_eq!(Address::from_str(&encoded).unwrap(), addr);
+  }
+
   #[bench]
   pub fn generate_address(bh: &mut Bencher) {
     let mut s = Secp256k1::new().unwrap();
@@ -215,4 +605,3 @@ mod tests {
     });
   }
 }
-
@@ -0,0 +1,155 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! A percentile-based [`FeeRate`] estimator built from mempool or recent-block samples.
+
+use alloc::vec::Vec;
+
+use super::FeeRate;
+use crate::weight::Weight;
+
+/// Aggregates `(FeeRate, Weight)` samples - e.g. mempool transactions or those in recent blocks -
+/// into a weight-weighted histogram, then answers "what fee rate is needed to land in the top
+/// `p` fraction of weight by fee rate".
+///
+/// Besides [`Self::add_sample`], which adds one sample at a time, samples can be passed as an
+/// iterator ([`Self::from_samples`], [`Self::extend`]) so that callers who already have
+/// mempool/histogram data in some other shape don't need to collect it first. The estimator
+/// stores its own copy of every sample.
+#[derive(Debug, Default, Clone)]
+pub struct FeeEstimator {
+    samples: Vec<(FeeRate, Weight)>,
+}
+
+impl FeeEstimator {
+    /// Creates an estimator with no samples.
+    pub fn new() -> Self { FeeEstimator { samples: Vec::new() } }
+
+    /// Builds an estimator from an iterator of `(fee_rate, weight)` samples.
+    pub fn from_samples<I: IntoIterator<Item = (FeeRate, Weight)>>(samples: I) -> Self {
+        FeeEstimator { samples: samples.into_iter().collect() }
+    }
+
+    /// Adds a single `(fee_rate, weight)` sample, e.g. one mempool or recent-block transaction.
+    pub fn add_sample(&mut self, fee_rate: FeeRate, weight: Weight) {
+        self.samples.push((fee_rate, weight));
+    }
+
+    /// Adds every sample yielded by `samples`.
+    pub fn extend<I: IntoIterator<Item = (FeeRate, Weight)>>(&mut self, samples: I) {
+        self.samples.extend(samples);
+    }
+
+    /// Estimates the fee rate needed to land in the top `target_percentile` fraction of sample
+    /// weight, where `target_percentile` is in `0.0..=1.0`.
+    ///
+    /// `target_percentile` is "how small a slice of the highest-paying weight you need to beat",
+    /// not a generic percentile of the sample population: a *smaller* `target_percentile` means
+    /// only a thin, high-paying slice of weight qualifies, so a *higher* fee rate is returned;
+    /// `1.0` walks all the way through the histogram and returns the lowest sampled fee rate.
+    /// This is why [`Self::fastest`] asks for a small percentile and [`Self::hour`] for a larger
+    /// one, even though `fastest` should return the highest fee rate of the three.
+    ///
+    /// Samples are sorted by fee rate ascending, then weight is accumulated from the high-fee
+    /// end until the running fraction of total weight reaches `target_percentile`; the fee rate
+    /// at that point is the estimate.
+    ///
+    /// Returns [`FeeRate::BROADCAST_MIN`] when every sample has zero weight or when the running
+    /// fraction never reaches `target_percentile` (a value above `1.0`, or NaN), and `None` when
+    /// there are no samples at all. The estimate is not otherwise clamped: it is below
+    /// [`FeeRate::BROADCAST_MIN`] whenever the sample at the boundary is.
+    pub fn estimate(&self, target_percentile: f64) -> Option<FeeRate> {
+        if self.samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = self.samples.clone();
+        sorted.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let total_weight: u64 = sorted.iter().map(|(_, weight)| weight.to_wu()).sum();
+        if total_weight == 0 {
+            return Some(FeeRate::BROADCAST_MIN);
+        }
+
+        let mut accumulated = 0u64;
+        for (fee_rate, weight) in sorted.iter().rev() {
+            accumulated += weight.to_wu();
+            if accumulated as f64 / total_weight as f64 >= target_percentile {
+                return Some(*fee_rate);
+            }
+        }
+
+        Some(FeeRate::BROADCAST_MIN)
+    }
+
+    /// Estimate for next-block, highest-priority inclusion: only the top 5% of weight by fee
+    /// rate needs to be beaten, so this returns the highest fee rate of the three.
+    pub fn fastest(&self) -> Option<FeeRate> { self.estimate(0.05) }
+
+    /// Estimate for inclusion within roughly half an hour, i.e. ~3 blocks: the top 25% of weight.
+    pub fn half_hour(&self) -> Option<FeeRate> { self.estimate(0.25) }
+
+    /// Estimate for inclusion within roughly an hour, i.e. ~6 blocks: the top 50% of weight.
+    pub fn hour(&self) -> Option<FeeRate> { self.estimate(0.5) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(sat_per_kwu: u64, wu: u64) -> (FeeRate, Weight) {
+        (FeeRate::from_sat_per_kwu(sat_per_kwu), Weight::from_wu(wu))
+    }
+
+    #[test]
+    fn empty_estimator_returns_none() {
+        let estimator = FeeEstimator::new();
+        assert_eq!(estimator.estimate(0.5), None);
+        assert_eq!(estimator.fastest(), None);
+    }
+
+    #[test]
+    fn estimate_picks_boundary_by_weight() {
+        let estimator = FeeEstimator::from_samples(vec![
+            sample(100, 100),
+            sample(200, 100),
+            sample(300, 100),
+            sample(400, 100),
+        ]);
+
+        // Top 25% of weight is the 400 sat/kwu sample.
+        assert_eq!(estimator.estimate(0.25), Some(FeeRate::from_sat_per_kwu(400)));
+        // Top 50% of weight spans the 300 and 400 sat/kwu samples.
+        assert_eq!(estimator.estimate(0.5), Some(FeeRate::from_sat_per_kwu(300)));
+        // The whole histogram is needed to reach 100%.
+        assert_eq!(estimator.estimate(1.0), Some(FeeRate::from_sat_per_kwu(100)));
+    }
+
+    #[test]
+    fn add_sample_and_extend_match_from_samples() {
+        let mut estimator = FeeEstimator::new();
+        estimator.add_sample(FeeRate::from_sat_per_kwu(100), Weight::from_wu(100));
+        estimator.extend(vec![sample(200, 100)]);
+
+        let expected = FeeEstimator::from_samples(vec![sample(100, 100), sample(200, 100)]);
+        assert_eq!(estimator.estimate(0.5), expected.estimate(0.5));
+    }
+
+    #[test]
+    fn convenience_percentiles_are_monotonic() {
+        let estimator = FeeEstimator::from_samples(
+            (1..=100).map(|i| sample(i, 1000)),
+        );
+
+        let fastest = estimator.fastest().unwrap();
+        let half_hour = estimator.half_hour().unwrap();
+        let hour = estimator.hour().unwrap();
+
+        // Faster confirmation must cost at least as much, never less.
+        assert!(fastest >= half_hour);
+        assert!(half_hour >= hour);
+
+        assert_eq!(fastest, FeeRate::from_sat_per_kwu(96));
+        assert_eq!(half_hour, FeeRate::from_sat_per_kwu(76));
+        assert_eq!(hour, FeeRate::from_sat_per_kwu(51));
+    }
+}
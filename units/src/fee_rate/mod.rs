@@ -5,8 +5,12 @@
 #[cfg(feature = "serde")]
 pub mod serde;
 
+mod estimator;
+
 use core::{fmt, ops};
 
+pub use self::estimator::FeeEstimator;
+
 #[cfg(feature = "arbitrary")]
 use arbitrary::{Arbitrary, Unstructured};
 
@@ -63,6 +67,15 @@ impl FeeRate {
     /// Constructs a new [`FeeRate`] from satoshis per kilo virtual bytes (1,000 vbytes).
     pub const fn from_sat_per_kvb(sat_kvb: u64) -> Self { FeeRate(sat_kvb / 4) }
 
+    /// Constructs a new [`FeeRate`] from a total `fee` paid for a transaction of the given
+    /// `weight`, returning [`None`] on overflow or if `weight` is zero.
+    ///
+    /// This is equivalent to [`Self::checked_div_by_weight`].
+    #[must_use]
+    pub const fn from_wu(fee: Amount, weight: Weight) -> Option<Self> {
+        Self::checked_div_by_weight(fee, weight)
+    }
+
     /// Returns raw fee rate.
     ///
     /// Can be used instead of `into()` to avoid inference issues.
@@ -121,6 +134,25 @@ impl FeeRate {
         }
     }
 
+    /// Checked weight division.
+    ///
+    /// Computes the fee rate paid by a transaction with the given `fee` and `weight`, i.e.
+    /// `fee_sat * 1000 / weight_wu`, returning [`None`] if `weight` is zero or if the
+    /// intermediate `fee_sat * 1000` overflows a `u64`.
+    ///
+    /// This is the conservative, overflow-checked counterpart to `Amount::div(Weight)`.
+    #[must_use]
+    pub const fn checked_div_by_weight(fee: Amount, weight: Weight) -> Option<Self> {
+        // No `?` operator in const context.
+        match fee.to_sat().checked_mul(1000) {
+            Some(mul_res) => match mul_res.checked_div(weight.to_wu()) {
+                Some(res) => Some(Self(res)),
+                None => None,
+            },
+            None => None,
+        }
+    }
+
     /// Checked addition.
     ///
     /// Computes `self + rhs` returning [`None`] if overflow occurred.
@@ -239,6 +271,22 @@ impl<'a> core::iter::Sum<&'a FeeRate> for FeeRate {
 
 crate::impl_parse_str_from_int_infallible!(FeeRate, u64, from_sat_per_kwu);
 
+// Note the leading `::`: this module's own `pub mod serde;` submodule would otherwise shadow the
+// extern `serde` crate here, resolving `serde::Serialize` etc. into that (private) submodule.
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for FeeRate {
+    fn serialize<S: ::serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        ::serde::Serialize::serialize(&self.to_sat_per_kwu(), s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for FeeRate {
+    fn deserialize<D: ::serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        Ok(FeeRate::from_sat_per_kwu(::serde::Deserialize::deserialize(d)?))
+    }
+}
+
 #[cfg(feature = "arbitrary")]
 impl<'a> Arbitrary<'a> for FeeRate {
     fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
@@ -444,6 +492,28 @@ mod tests {
         assert_eq!(fee, Amount::from_sat(330));
     }
 
+    #[test]
+    fn checked_div_by_weight() {
+        let fee_rate =
+            FeeRate::checked_div_by_weight(Amount::from_sat(329), Weight::from_wu(381)).unwrap();
+        assert_eq!(fee_rate, FeeRate(863));
+
+        let fee_rate = FeeRate::checked_div_by_weight(Amount::from_sat(1), Weight::ZERO);
+        assert!(fee_rate.is_none());
+
+        let fee_rate = FeeRate::checked_div_by_weight(Amount::MAX, Weight::from_wu(1));
+        assert!(fee_rate.is_none());
+    }
+
+    #[test]
+    fn from_wu() {
+        let fee_rate = FeeRate::from_wu(Amount::from_sat(329), Weight::from_wu(381)).unwrap();
+        assert_eq!(fee_rate, FeeRate(863));
+
+        let fee_rate = FeeRate::from_wu(Amount::from_sat(1), Weight::ZERO);
+        assert!(fee_rate.is_none());
+    }
+
     #[test]
     fn checked_div() {
         let fee_rate = FeeRate(10).checked_div(10).expect("expected feerate in sat/kwu");
@@ -452,4 +522,28 @@ mod tests {
         let fee_rate = FeeRate(10).checked_div(0);
         assert!(fee_rate.is_none());
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn feerate_serde_roundtrips_as_sat_per_kwu() {
+        let fee_rate = FeeRate::from_sat_per_kwu(749);
+        let json = serde_json::to_string(&fee_rate).unwrap();
+        assert_eq!(json, "749");
+        assert_eq!(serde_json::from_str::<FeeRate>(&json).unwrap(), fee_rate);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn feerate_serde_as_sat_per_vb() {
+        #[derive(::serde::Serialize, ::serde::Deserialize, PartialEq, Debug)]
+        struct Fees {
+            #[serde(with = "crate::fee_rate::serde::as_sat_per_vb")]
+            rate: FeeRate,
+        }
+
+        let fees = Fees { rate: FeeRate::from_sat_per_vb(10).unwrap() };
+        let json = serde_json::to_string(&fees).unwrap();
+        assert_eq!(json, r#"{"rate":10}"#);
+        assert_eq!(serde_json::from_str::<Fees>(&json).unwrap(), fees);
+    }
 }
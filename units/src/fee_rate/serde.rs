@@ -0,0 +1,54 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Alternative serde serializations for [`FeeRate`].
+//!
+//! The default implementation of [`serde::Serialize`] and [`serde::Deserialize`] on [`FeeRate`]
+//! (defined in the parent module) round-trips the raw `sat/kwu` value. This module offers opt-in
+//! `with`-style submodules for other common units, each usable via `#[serde(with = "...")]`.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::FeeRate;
+
+/// Serializes and deserializes [`FeeRate`] as sat/vB, rounding up on serialization.
+pub mod as_sat_per_vb {
+    use super::*;
+
+    /// Serializes a [`FeeRate`] as sat/vB.
+    pub fn serialize<S: Serializer>(fee_rate: &FeeRate, s: S) -> Result<S::Ok, S::Error> {
+        u64::serialize(&fee_rate.to_sat_per_vb_ceil(), s)
+    }
+
+    /// Deserializes a [`FeeRate`] from sat/vB.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the sat/vB value overflows when converted to sat/kwu.
+    pub fn deserialize<'d, D: Deserializer<'d>>(d: D) -> Result<FeeRate, D::Error> {
+        let sat_per_vb = u64::deserialize(d)?;
+        FeeRate::from_sat_per_vb(sat_per_vb).ok_or_else(|| {
+            serde::de::Error::custom(format!(
+                "overflow occurred while converting {} sat/vB to sat/kwu",
+                sat_per_vb
+            ))
+        })
+    }
+}
+
+/// Serializes and deserializes [`FeeRate`] as sat/kwu.
+///
+/// This matches the default (de)serialization of [`FeeRate`] but is available explicitly for use
+/// with `#[serde(with = "...")]` on fields where the unit should be spelled out.
+pub mod as_sat_per_kwu {
+    use super::*;
+
+    /// Serializes a [`FeeRate`] as sat/kwu.
+    pub fn serialize<S: Serializer>(fee_rate: &FeeRate, s: S) -> Result<S::Ok, S::Error> {
+        u64::serialize(&fee_rate.to_sat_per_kwu(), s)
+    }
+
+    /// Deserializes a [`FeeRate`] from sat/kwu.
+    pub fn deserialize<'d, D: Deserializer<'d>>(d: D) -> Result<FeeRate, D::Error> {
+        Ok(FeeRate::from_sat_per_kwu(u64::deserialize(d)?))
+    }
+}